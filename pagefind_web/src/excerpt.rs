@@ -0,0 +1,21 @@
+/// Picks the start offset of the densest cluster of matched word locations,
+/// so the UI can render a `window`-word excerpt around the best match.
+pub fn calculate_excerpt(locs: &[u32], window: u32) -> u32 {
+    if locs.is_empty() {
+        return 0;
+    }
+
+    let mut best_start = locs[0];
+    let mut best_count = 0;
+
+    for &start in locs {
+        let end = start + window;
+        let count = locs.iter().filter(|&&loc| loc >= start && loc < end).count();
+        if count > best_count {
+            best_count = count;
+            best_start = start;
+        }
+    }
+
+    best_start.saturating_sub(window / 6)
+}
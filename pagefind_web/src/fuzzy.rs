@@ -0,0 +1,128 @@
+use std::collections::HashSet;
+
+/// How many edits a typo is allowed to be away from a dictionary word
+/// before we stop considering it a match, mirroring the common
+/// typo-tolerance rule of thumb (short words tolerate fewer mistakes).
+pub fn max_edit_distance(term_len: usize) -> usize {
+    if term_len <= 5 {
+        1
+    } else {
+        2
+    }
+}
+
+/// Cheap pre-filter: a word can only be within `max_distance` edits of
+/// `term` if their lengths are close and their leading `max_distance + 1`
+/// characters share at least one character. This lets us skip the
+/// O(n*m) edit distance check for most of the dictionary before running
+/// it on the handful of plausible candidates. Requiring an *exact* first
+/// character (rather than an overlap across the first few) would reject
+/// a single-character typo on the word's first letter — e.g. "bat" for
+/// "cat" — even though it's within tolerance.
+fn could_be_within_distance(term: &str, candidate: &str, max_distance: usize) -> bool {
+    let term_len = term.chars().count();
+    let candidate_len = candidate.chars().count();
+    let len_diff = term_len.abs_diff(candidate_len);
+    if len_diff > max_distance {
+        return false;
+    }
+    let prefix_len = max_distance + 1;
+    let term_prefix: HashSet<char> = term.chars().take(prefix_len).collect();
+    candidate.chars().take(prefix_len).any(|c| term_prefix.contains(&c))
+}
+
+/// Classic Levenshtein edit distance, bailing out early (as
+/// `max_distance + 1`, a value the caller only needs to know exceeds the
+/// cutoff) once it's clear the true distance will be too large.
+fn edit_distance(a: &str, b: &str, max_distance: usize) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    let mut previous_row: Vec<usize> = (0..=b.len()).collect();
+    for (i, &a_char) in a.iter().enumerate() {
+        let mut current_row = vec![i + 1];
+        let mut row_min = current_row[0];
+        for (j, &b_char) in b.iter().enumerate() {
+            let cost = if a_char == b_char { 0 } else { 1 };
+            let value = (previous_row[j] + cost)
+                .min(previous_row[j + 1] + 1)
+                .min(current_row[j] + 1);
+            row_min = row_min.min(value);
+            current_row.push(value);
+        }
+        if row_min > max_distance {
+            return max_distance + 1;
+        }
+        previous_row = current_row;
+    }
+
+    previous_row[b.len()]
+}
+
+/// Finds dictionary words within the typo-tolerance edit distance of
+/// `term`, for use as fallback matches when `term` isn't in the index
+/// at all. Ordered by edit distance (closest typo fix first), then
+/// lexicographically, so the result — and whichever candidate a caller
+/// reports as "searched instead for" — is deterministic regardless of
+/// the dictionary's hash map iteration order.
+pub fn fuzzy_candidates<'a>(term: &str, dictionary: impl Iterator<Item = &'a String>) -> Vec<String> {
+    let max_distance = max_edit_distance(term.chars().count());
+
+    let mut candidates: Vec<(usize, &str)> = dictionary
+        .filter(|candidate| could_be_within_distance(term, candidate, max_distance))
+        .map(|candidate| (edit_distance(term, candidate, max_distance), candidate.as_str()))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .collect();
+
+    candidates.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(b.1)));
+    candidates.into_iter().map(|(_, candidate)| candidate.to_owned()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn max_edit_distance_boundary_at_length_five() {
+        assert_eq!(max_edit_distance(5), 1);
+        assert_eq!(max_edit_distance(6), 2);
+    }
+
+    #[test]
+    fn finds_a_single_character_typo() {
+        let dictionary = vec!["search".to_owned(), "research".to_owned(), "unrelated".to_owned()];
+        let candidates = fuzzy_candidates("serch", dictionary.iter());
+        assert_eq!(candidates, vec!["search".to_owned()]);
+    }
+
+    #[test]
+    fn ignores_words_beyond_the_edit_distance_cutoff() {
+        let dictionary = vec!["search".to_owned()];
+        // At length 4, "serh" only tolerates 1 edit; "search" is 2 edits
+        // away (missing 'a' and 'c'), so it should not be a candidate.
+        let candidates = fuzzy_candidates("serh", dictionary.iter());
+        assert!(candidates.is_empty());
+    }
+
+    #[test]
+    fn finds_a_typo_on_the_first_letter() {
+        // "bat" for "cat" is a single substitution, but on the first
+        // character rather than the middle/end — the prefilter must not
+        // reject it before edit distance is even computed.
+        let dictionary = vec!["cat".to_owned(), "unrelated".to_owned()];
+        let candidates = fuzzy_candidates("bat", dictionary.iter());
+        assert_eq!(candidates, vec!["cat".to_owned()]);
+    }
+
+    #[test]
+    fn orders_candidates_by_distance_then_lexicographically() {
+        let dictionary = vec!["cut".to_owned(), "cats".to_owned(), "car".to_owned()];
+        // "car", "cats", and "cut" are each exactly 1 edit from "cat", so
+        // ties should break lexicographically.
+        let candidates = fuzzy_candidates("cat", dictionary.iter());
+        assert_eq!(
+            candidates,
+            vec!["car".to_owned(), "cats".to_owned(), "cut".to_owned()]
+        );
+    }
+}
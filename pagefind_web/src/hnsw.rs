@@ -0,0 +1,169 @@
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+/// An HNSW (Hierarchical Navigable Small World) proximity graph over
+/// per-page embeddings, used for semantic search. `layers[0]` is the
+/// densest, base layer; higher layers are progressively sparser
+/// shortcuts used to get close to the query quickly before the base
+/// layer is searched (visiting at most `ef` nodes).
+#[derive(Default)]
+pub struct HnswGraph {
+    entry_point: Option<u32>,
+    layers: Vec<HashMap<u32, Vec<u32>>>,
+    vectors: HashMap<u32, Vec<f32>>,
+}
+
+impl HnswGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert_layer(&mut self, layer: usize, node: u32, neighbors: Vec<u32>) {
+        if self.layers.len() <= layer {
+            self.layers.resize_with(layer + 1, HashMap::new);
+        }
+        self.layers[layer].insert(node, neighbors);
+    }
+
+    pub fn insert_vector(&mut self, page: u32, vector: Vec<f32>) {
+        self.vectors.insert(page, vector);
+    }
+
+    pub fn set_entry_point(&mut self, page: u32) {
+        self.entry_point = Some(page);
+    }
+
+    pub fn similarity_to(&self, query: &[f32], page: u32) -> Option<f32> {
+        self.vectors.get(&page).map(|vector| cosine_similarity(query, vector))
+    }
+
+    /// Greedily descends from the entry point through the upper layers,
+    /// then runs a bounded beam search (candidate set capped at `ef`) over
+    /// the base layer, returning the `limit` nearest pages.
+    pub fn search(&self, query: &[f32], ef: usize, limit: usize) -> Vec<(u32, f32)> {
+        let Some(entry_point) = self.entry_point else {
+            return Vec::new();
+        };
+
+        let mut current = entry_point;
+        let mut current_score = self.similarity_to(query, current).unwrap_or(f32::MIN);
+
+        for layer in (1..self.layers.len()).rev() {
+            loop {
+                let mut moved = false;
+                if let Some(neighbors) = self.layers[layer].get(&current) {
+                    for &neighbor in neighbors {
+                        let score = self.similarity_to(query, neighbor).unwrap_or(f32::MIN);
+                        if score > current_score {
+                            current = neighbor;
+                            current_score = score;
+                            moved = true;
+                        }
+                    }
+                }
+                if !moved {
+                    break;
+                }
+            }
+        }
+
+        let mut visited: HashSet<u32> = HashSet::new();
+        visited.insert(current);
+        let mut candidates = vec![(current, current_score)];
+        let mut frontier = vec![current];
+
+        while let Some(page) = frontier.pop() {
+            if visited.len() >= ef {
+                break;
+            }
+            let Some(neighbors) = self.layers.first().and_then(|base| base.get(&page)) else {
+                continue;
+            };
+            for &neighbor in neighbors {
+                if visited.len() >= ef {
+                    break;
+                }
+                if visited.insert(neighbor) {
+                    let score = self.similarity_to(query, neighbor).unwrap_or(f32::MIN);
+                    candidates.push((neighbor, score));
+                    frontier.push(neighbor);
+                }
+            }
+        }
+
+        candidates.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(Ordering::Equal));
+        candidates.truncate(limit);
+        candidates
+    }
+}
+
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identical_vectors_are_maximally_similar() {
+        assert!((cosine_similarity(&[1.0, 2.0, 3.0], &[1.0, 2.0, 3.0]) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn opposite_vectors_are_minimally_similar() {
+        assert_eq!(cosine_similarity(&[1.0, 0.0], &[-1.0, 0.0]), -1.0);
+    }
+
+    #[test]
+    fn orthogonal_vectors_have_zero_similarity() {
+        assert_eq!(cosine_similarity(&[1.0, 0.0], &[0.0, 1.0]), 0.0);
+    }
+
+    #[test]
+    fn a_zero_vector_has_zero_similarity_rather_than_dividing_by_zero() {
+        assert_eq!(cosine_similarity(&[0.0, 0.0], &[1.0, 2.0]), 0.0);
+    }
+
+    fn graph_with(entry: u32, vectors: &[(u32, Vec<f32>)], neighbors: &[(u32, Vec<u32>)]) -> HnswGraph {
+        let mut graph = HnswGraph::new();
+        for (page, vector) in vectors {
+            graph.insert_vector(*page, vector.clone());
+        }
+        for (node, neighbors) in neighbors {
+            graph.insert_layer(0, *node, neighbors.clone());
+        }
+        graph.set_entry_point(entry);
+        graph
+    }
+
+    #[test]
+    fn search_walks_the_base_layer_toward_the_nearest_neighbor() {
+        // Entry point 0 is far from the query; its neighbor 1 is an exact
+        // match and should surface as the top (and only requested) result.
+        let graph = graph_with(
+            0,
+            &[(0, vec![1.0, 0.0]), (1, vec![0.0, 1.0]), (2, vec![-1.0, 0.0])],
+            &[(0, vec![1, 2]), (1, vec![0]), (2, vec![0])],
+        );
+
+        let results = graph.search(&[0.0, 1.0], 10, 1);
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, 1);
+        assert_eq!(results[0].1, 1.0);
+    }
+
+    #[test]
+    fn search_on_an_empty_graph_returns_no_results() {
+        let graph = HnswGraph::new();
+        assert_eq!(graph.search(&[1.0, 0.0], 10, 5), Vec::new());
+    }
+}
@@ -0,0 +1,69 @@
+use crate::util::{ByteReader, IndexError};
+use crate::{PageWord, SearchIndex};
+
+/// A chunk of word postings, keyed by the dictionary range covered (see
+/// `IndexChunk`).
+const CHUNK_KIND_WORDS: u8 = 0;
+/// A chunk of per-page embeddings plus their HNSW graph layers, used for
+/// semantic search.
+const CHUNK_KIND_VECTORS: u8 = 1;
+
+impl SearchIndex {
+    /// Decodes one on-demand index chunk and merges it into the
+    /// in-memory index. The leading byte is a discriminant: word chunks
+    /// carry postings for a slice of the dictionary, vector chunks carry
+    /// page embeddings and their HNSW graph layers.
+    pub fn decode_index_chunk(&mut self, bytes: &[u8]) -> Result<(), IndexError> {
+        let mut reader = ByteReader::new(bytes);
+        let kind = reader.read_u8()?;
+
+        match kind {
+            CHUNK_KIND_WORDS => self.decode_word_chunk(&mut reader),
+            CHUNK_KIND_VECTORS => self.decode_vector_chunk(&mut reader),
+            _ => Err(IndexError::Corrupt("unknown index chunk kind")),
+        }
+    }
+
+    fn decode_word_chunk(&mut self, reader: &mut ByteReader<'_>) -> Result<(), IndexError> {
+        let word_count = reader.read_u32()? as usize;
+        for _ in 0..word_count {
+            let word = reader.read_string()?;
+            let page_count = reader.read_u32()? as usize;
+            let mut pages = Vec::with_capacity(page_count);
+            for _ in 0..page_count {
+                let page = reader.read_u32()?;
+                let locs = reader.read_u32_vec()?;
+                pages.push(PageWord { page, locs });
+            }
+            self.words.entry(word).or_default().extend(pages);
+        }
+
+        Ok(())
+    }
+
+    fn decode_vector_chunk(&mut self, reader: &mut ByteReader<'_>) -> Result<(), IndexError> {
+        let vector_count = reader.read_u32()? as usize;
+        for _ in 0..vector_count {
+            let page = reader.read_u32()?;
+            let vector = reader.read_f32_vec()?;
+            self.vector_index.insert_vector(page, vector);
+        }
+
+        let layer_count = reader.read_u32()? as usize;
+        for layer in 0..layer_count {
+            let node_count = reader.read_u32()? as usize;
+            for _ in 0..node_count {
+                let node = reader.read_u32()?;
+                let neighbors = reader.read_u32_vec()?;
+                self.vector_index.insert_layer(layer, node, neighbors);
+            }
+        }
+
+        let has_entry_point = reader.read_u8()? == 1;
+        if has_entry_point {
+            self.vector_index.set_entry_point(reader.read_u32()?);
+        }
+
+        Ok(())
+    }
+}
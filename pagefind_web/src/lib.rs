@@ -7,14 +7,22 @@ use std::collections::HashMap;
 
 use bit_set::BitSet;
 use excerpt::calculate_excerpt;
+use fuzzy::fuzzy_candidates;
 use rust_stemmers::{Algorithm, Stemmer}; // TODO: too big
 use wasm_bindgen::prelude::*;
 
 mod excerpt;
+mod fuzzy;
+mod hnsw;
 mod index;
 mod metadata;
+mod query;
 mod util;
 
+use hnsw::HnswGraph;
+use query::QueryNode;
+
+#[derive(Clone)]
 pub struct PageWord {
     page: u32,
     locs: Vec<u32>,
@@ -29,12 +37,21 @@ pub struct IndexChunk {
 pub struct SearchIndex {
     web_version: &'static str,
     generator_version: Option<String>,
+    language: String,
     pages: Vec<String>,
+    page_lengths: Vec<u32>,
+    average_page_length: f32,
     chunks: Vec<IndexChunk>,
     stops: Vec<String>,
     words: HashMap<String, Vec<PageWord>>,
+    synonyms: HashMap<String, Vec<String>>,
+    vector_index: HnswGraph,
 }
 
+// BM25 ranking constants, per Robertson/Zaragoza defaults.
+const BM25_K1: f32 = 1.2;
+const BM25_B: f32 = 0.75;
+
 #[cfg(debug_assertions)]
 #[wasm_bindgen]
 extern "C" {
@@ -54,10 +71,15 @@ pub fn init_pagefind(metadata_bytes: &[u8]) -> *mut SearchIndex {
     let mut search_index = SearchIndex {
         web_version: env!("CARGO_PKG_VERSION"),
         generator_version: None,
+        language: "en".into(),
         pages: Vec::new(),
+        page_lengths: Vec::new(),
+        average_page_length: 0.0,
         chunks: Vec::new(),
         stops: Vec::new(),
         words: HashMap::new(),
+        synonyms: HashMap::new(),
+        vector_index: HnswGraph::new(),
     };
 
     match search_index.decode_metadata(metadata_bytes) {
@@ -86,6 +108,79 @@ pub fn load_index_chunk(ptr: *mut SearchIndex, chunk_bytes: &[u8]) -> *mut Searc
     }
 }
 
+/// All term strings a query term should also be searched as: its synonyms,
+/// and a concatenation with the following token (for split compound words
+/// like "wi fi"). Concat-compound words like "wifi" are handled separately
+/// by `compound_split_variants`/`compound_split_matches`, since a page that
+/// only contains one half of the split isn't a real match for the whole
+/// term.
+fn term_variants(term: &str, next_term: Option<&str>, synonyms: &HashMap<String, Vec<String>>) -> Vec<String> {
+    let mut variants = vec![term.to_owned()];
+
+    if let Some(synonym_group) = synonyms.get(term) {
+        variants.extend(synonym_group.iter().cloned());
+    }
+
+    if let Some(next) = next_term {
+        variants.push(format!("{term}{next}"));
+    }
+
+    variants
+}
+
+/// The term broken at every internal position (for concat compound words
+/// like "wifi"). These are not real term matches on their own — they're
+/// only useful to `request_indexes` for making sure the chunks that could
+/// contain either half are loaded before `compound_split_matches` looks
+/// for adjacent occurrences of both halves.
+fn compound_split_variants(term: &str) -> Vec<String> {
+    let chars: Vec<char> = term.chars().collect();
+    let mut variants = Vec::new();
+    for split_at in 1..chars.len() {
+        variants.push(chars[..split_at].iter().collect());
+        variants.push(chars[split_at..].iter().collect());
+    }
+    variants
+}
+
+/// Matches a concat compound word (like "wifi") against a page that
+/// actually spells it as two adjacent words (like "wi fi"), by requiring
+/// the split halves to appear at consecutive locations on the same page —
+/// the same adjacency rule `evaluate_phrase` uses for quoted phrases.
+/// Without this, splitting "email" into "e"/"mail" and OR-ing both halves
+/// into the term's matches would match any page containing "mail" alone.
+fn compound_split_matches(search_index: &SearchIndex, term: &str) -> Vec<PageWord> {
+    let chars: Vec<char> = term.chars().collect();
+    let mut matched = Vec::new();
+
+    for split_at in 1..chars.len() {
+        let left: String = chars[..split_at].iter().collect();
+        let right: String = chars[split_at..].iter().collect();
+        let (Some(left_words), Some(right_words)) =
+            (search_index.words.get(&left), search_index.words.get(&right))
+        else {
+            continue;
+        };
+        let right_locs: HashMap<u32, &Vec<u32>> = right_words.iter().map(|pw| (pw.page, &pw.locs)).collect();
+
+        for left_word in left_words {
+            let Some(locs) = right_locs.get(&left_word.page) else {
+                continue;
+            };
+            for &loc in &left_word.locs {
+                if locs.contains(&(loc + 1)) {
+                    matched.push(PageWord {
+                        page: left_word.page,
+                        locs: vec![loc, loc + 1],
+                    });
+                }
+            }
+        }
+    }
+
+    matched
+}
+
 #[wasm_bindgen]
 pub fn request_indexes(ptr: *mut SearchIndex, query: &str) -> String {
     #[cfg(debug_assertions)]
@@ -93,72 +188,201 @@ pub fn request_indexes(ptr: *mut SearchIndex, query: &str) -> String {
 
     let search_index = unsafe { Box::from_raw(ptr) };
     let mut indexes = Vec::new();
-    let terms = query.split(' ');
 
-    for term in terms {
+    let request_chunks_for = |term: &str, indexes: &mut Vec<String>| {
         let term_index = search_index
             .chunks
             .iter()
-            .find(|chunk| term >= &chunk.from && term <= &chunk.to);
+            .find(|chunk| term >= chunk.from.as_str() && term <= chunk.to.as_str());
         if let Some(index) = term_index {
             indexes.push(index.hash.clone())
         }
+    };
+
+    if query::has_operators(query) {
+        let stemmer = stemmer_for_language(&search_index.language);
+        let mut words = Vec::new();
+        query::collect_terms(&query::parse_query(query), &mut words);
+        for word in &words {
+            let term = stem_term(stemmer.as_ref(), word);
+            for variant in term_variants(&term, None, &search_index.synonyms) {
+                request_chunks_for(&variant, &mut indexes);
+            }
+            for variant in compound_split_variants(&term) {
+                request_chunks_for(&variant, &mut indexes);
+            }
+        }
+    } else {
+        let stemmer = stemmer_for_language(&search_index.language);
+        let terms: Vec<String> = query
+            .split(' ')
+            .map(|term| stem_term(stemmer.as_ref(), term))
+            .collect();
+        for (i, term) in terms.iter().enumerate() {
+            for variant in term_variants(term, terms.get(i + 1).map(String::as_str), &search_index.synonyms) {
+                request_chunks_for(&variant, &mut indexes);
+            }
+            for variant in compound_split_variants(term) {
+                request_chunks_for(&variant, &mut indexes);
+            }
+        }
     }
 
+    indexes.sort();
+    indexes.dedup();
+
     let _ = Box::into_raw(search_index);
     indexes.join(" ")
 }
 
-#[wasm_bindgen]
-pub fn search(ptr: *mut SearchIndex, query: &str) -> String {
-    let search_index = unsafe { Box::from_raw(ptr) };
+/// Inverse document frequency for a term matched by `matching_pages` out of
+/// `total_pages`, per the BM25 Okapi formulation.
+fn bm25_idf(total_pages: usize, matching_pages: usize) -> f32 {
+    let n = total_pages as f32;
+    let n_t = matching_pages as f32;
+    (1.0 + (n - n_t + 0.5) / (n_t + 0.5)).ln()
+}
 
-    if let Some(generator_version) = search_index.generator_version.as_ref() {
-        if generator_version != search_index.web_version {
-            let _ = Box::into_raw(search_index);
-            return "ERROR: Version mismatch".into();
-        }
+/// A single term's contribution to a page's BM25 score.
+fn bm25_term_score(idf: f32, term_frequency: usize, doc_length: f32, average_doc_length: f32) -> f32 {
+    let f = term_frequency as f32;
+    let norm = if average_doc_length > 0.0 {
+        doc_length / average_doc_length
+    } else {
+        1.0
+    };
+    idf * (f * (BM25_K1 + 1.0)) / (f + BM25_K1 * (1.0 - BM25_B + BM25_B * norm))
+}
+
+/// Maps a stored ISO language code to the matching Snowball stemmer,
+/// falling back to leaving terms unstemmed for languages Snowball doesn't
+/// cover.
+fn stemmer_for_language(language: &str) -> Option<Stemmer> {
+    let algorithm = match language {
+        "ar" => Algorithm::Arabic,
+        "da" => Algorithm::Danish,
+        "nl" => Algorithm::Dutch,
+        "en" => Algorithm::English,
+        "fi" => Algorithm::Finnish,
+        "fr" => Algorithm::French,
+        "de" => Algorithm::German,
+        "el" => Algorithm::Greek,
+        "hu" => Algorithm::Hungarian,
+        "it" => Algorithm::Italian,
+        "no" => Algorithm::Norwegian,
+        "pt" => Algorithm::Portuguese,
+        "ro" => Algorithm::Romanian,
+        "ru" => Algorithm::Russian,
+        "es" => Algorithm::Spanish,
+        "sv" => Algorithm::Swedish,
+        "ta" => Algorithm::Tamil,
+        "tr" => Algorithm::Turkish,
+        _ => return None,
+    };
+    Some(Stemmer::create(algorithm))
+}
+
+/// Stems `term` using the index's configured language, or returns it
+/// unchanged when the language has no Snowball stemmer available.
+fn stem_term(stemmer: Option<&Stemmer>, term: &str) -> String {
+    match stemmer {
+        Some(stemmer) => stemmer.stem(term).into_owned(),
+        None => term.to_owned(),
     }
+}
 
-    let terms = query.split(' ');
-    // TODO: i18n
-    let en_stemmer = Stemmer::create(Algorithm::English);
+/// The outcome of matching a query's keyword terms against the index:
+/// which pages survived the AND across terms, each page's accumulated
+/// BM25 score, the matched postings (for excerpting), and any fuzzy
+/// spelling corrections that were applied.
+struct KeywordMatches {
+    results: BitSet,
+    scores: HashMap<usize, f32>,
+    words: Vec<(f32, PageWord)>,
+    corrections: Vec<(String, String)>,
+}
 
-    #[cfg(debug_assertions)]
-    debug_log(&format! {"Searching {:?}", query});
+fn keyword_search(search_index: &SearchIndex, query: &str, exact: bool) -> Option<KeywordMatches> {
+    let stemmer = stemmer_for_language(&search_index.language);
+    let terms: Vec<String> = query
+        .split(' ')
+        .map(|term| stem_term(stemmer.as_ref(), term))
+        .collect();
 
+    let page_count = search_index.pages.len();
     let mut maps = Vec::new();
     let mut words = Vec::new();
-    for term in terms {
-        let term = en_stemmer.stem(term).into_owned();
-        if let Some(word_index) = search_index.words.get(&term) {
-            words.extend(word_index);
-            let mut set = BitSet::new();
-            for page in word_index {
-                set.insert(page.page as usize);
+    let mut corrections: Vec<(String, String)> = Vec::new();
+    for (i, term) in terms.iter().enumerate() {
+        let variants = term_variants(term, terms.get(i + 1).map(String::as_str), &search_index.synonyms);
+        let mut matched_words: Vec<PageWord> = Vec::new();
+        for variant in &variants {
+            if let Some(word_index) = search_index.words.get(variant) {
+                matched_words.extend(word_index.iter().cloned());
+            }
+        }
+        matched_words.extend(compound_split_matches(search_index, term));
+        // Only the whole, unsplit term is a real typo candidate — the
+        // split/concat variants are themselves short fragments that would
+        // otherwise get fuzzy-matched against unrelated dictionary words.
+        if matched_words.is_empty() && !exact {
+            let candidates = fuzzy_candidates(term, search_index.words.keys());
+            for candidate in &candidates {
+                if let Some(word_index) = search_index.words.get(candidate) {
+                    matched_words.extend(word_index.iter().cloned());
+                }
+            }
+            if let Some(corrected) = candidates.first() {
+                corrections.push((term.clone(), corrected.clone()));
             }
-            maps.push(set);
         }
+        if matched_words.is_empty() {
+            continue;
+        }
+        let idf = bm25_idf(page_count, matched_words.len());
+        let mut set = BitSet::new();
+        for page in &matched_words {
+            set.insert(page.page as usize);
+        }
+        words.extend(matched_words.into_iter().map(|page_word| (idf, page_word)));
+        maps.push(set);
     }
 
     let mut maps = maps.drain(..);
-    let mut results = if let Some(map) = maps.next() {
-        map
-    } else {
-        let _ = Box::into_raw(search_index);
-        return "".into();
-    };
-
+    let mut results = maps.next()?;
     for map in maps {
         results.intersect_with(&map);
     }
 
+    let mut scores: HashMap<usize, f32> = HashMap::new();
+    for (idf, page_word) in &words {
+        let page = page_word.page as usize;
+        if !results.contains(page) {
+            continue;
+        }
+        let dl = *search_index.page_lengths.get(page).unwrap_or(&0) as f32;
+        let score = bm25_term_score(*idf, page_word.locs.len(), dl, search_index.average_page_length);
+        *scores.entry(page).or_insert(0.0) += score;
+    }
+
+    Some(KeywordMatches {
+        results,
+        scores,
+        words,
+        corrections,
+    })
+}
+
+/// Renders the ranked page ids as the `path@excerpt_start,window@locs`
+/// records the JS layer expects, using `words` to find each page's
+/// matched locations for excerpting.
+fn format_page_results(search_index: &SearchIndex, ranked_pages: &[usize], words: &[(f32, &PageWord)]) -> String {
     let mut pages: Vec<String> = vec![];
 
-    for page in results.iter() {
+    for &page in ranked_pages {
         let locs: Vec<u32> = words
             .iter()
-            .filter_map(|p| {
+            .filter_map(|(_, p)| {
                 if p.page as usize == page {
                     Some(p.locs.clone())
                 } else {
@@ -178,7 +402,237 @@ pub fn search(ptr: *mut SearchIndex, query: &str) -> String {
                 .join(",")
         ));
     }
-    let o = pages.join(" ");
+
+    pages.join(" ")
+}
+
+/// The pages matched by a (sub-)query and the postings backing them,
+/// for scoring and excerpting. Built up by `evaluate_query` as it walks
+/// a `QueryNode` tree.
+struct EvalResult {
+    pages: BitSet,
+    postings: Vec<(f32, PageWord)>,
+}
+
+impl EvalResult {
+    fn empty() -> Self {
+        Self {
+            pages: BitSet::new(),
+            postings: Vec::new(),
+        }
+    }
+}
+
+fn evaluate_term(search_index: &SearchIndex, word: &str, exact: bool, corrections: &mut Vec<(String, String)>) -> EvalResult {
+    let stemmer = stemmer_for_language(&search_index.language);
+    let term = stem_term(stemmer.as_ref(), word);
+
+    let mut matched: Vec<PageWord> = Vec::new();
+    for variant in term_variants(&term, None, &search_index.synonyms) {
+        if let Some(word_index) = search_index.words.get(&variant) {
+            matched.extend(word_index.iter().cloned());
+        }
+    }
+    matched.extend(compound_split_matches(search_index, &term));
+    // Only the whole, unsplit term is a real typo candidate — the
+    // split/concat variants are themselves short fragments that would
+    // otherwise get fuzzy-matched against unrelated dictionary words.
+    if matched.is_empty() && !exact {
+        let candidates = fuzzy_candidates(&term, search_index.words.keys());
+        for candidate in &candidates {
+            if let Some(word_index) = search_index.words.get(candidate) {
+                matched.extend(word_index.iter().cloned());
+            }
+        }
+        if let Some(corrected) = candidates.first() {
+            corrections.push((term.clone(), corrected.clone()));
+        }
+    }
+
+    if matched.is_empty() {
+        return EvalResult::empty();
+    }
+
+    let idf = bm25_idf(search_index.pages.len(), matched.len());
+    let mut pages = BitSet::new();
+    for page_word in &matched {
+        pages.insert(page_word.page as usize);
+    }
+
+    EvalResult {
+        pages,
+        postings: matched.into_iter().map(|page_word| (idf, page_word)).collect(),
+    }
+}
+
+/// A phrase only matches a page when every word's locations line up as
+/// consecutive positions, so `loc[i + 1] == loc[i] + 1` across the whole
+/// phrase.
+fn evaluate_phrase(search_index: &SearchIndex, words: &[String]) -> EvalResult {
+    if words.is_empty() {
+        return EvalResult::empty();
+    }
+
+    let stemmer = stemmer_for_language(&search_index.language);
+    let stemmed: Vec<String> = words.iter().map(|word| stem_term(stemmer.as_ref(), word)).collect();
+
+    let Some(per_word_postings): Option<Vec<&Vec<PageWord>>> =
+        stemmed.iter().map(|word| search_index.words.get(word)).collect()
+    else {
+        return EvalResult::empty();
+    };
+
+    let per_word_locs: Vec<HashMap<u32, &Vec<u32>>> = per_word_postings
+        .iter()
+        .map(|postings| postings.iter().map(|page_word| (page_word.page, &page_word.locs)).collect())
+        .collect();
+
+    let mut pages = BitSet::new();
+    let mut matched: Vec<PageWord> = Vec::new();
+
+    'candidates: for &page in per_word_locs[0].keys() {
+        if !per_word_locs.iter().all(|locs| locs.contains_key(&page)) {
+            continue;
+        }
+        for &start in per_word_locs[0][&page] {
+            let is_consecutive = per_word_locs
+                .iter()
+                .enumerate()
+                .skip(1)
+                .all(|(offset, locs)| locs[&page].contains(&(start + offset as u32)));
+            if is_consecutive {
+                pages.insert(page as usize);
+                let locs = (0..per_word_locs.len() as u32).map(|offset| start + offset).collect();
+                matched.push(PageWord { page, locs });
+                continue 'candidates;
+            }
+        }
+    }
+
+    if matched.is_empty() {
+        return EvalResult::empty();
+    }
+
+    let idf = bm25_idf(search_index.pages.len(), matched.len());
+    EvalResult {
+        pages,
+        postings: matched.into_iter().map(|page_word| (idf, page_word)).collect(),
+    }
+}
+
+fn evaluate_query(search_index: &SearchIndex, node: &QueryNode, exact: bool, corrections: &mut Vec<(String, String)>) -> EvalResult {
+    match node {
+        QueryNode::Term(word) => evaluate_term(search_index, word, exact, corrections),
+        QueryNode::Phrase(words) => evaluate_phrase(search_index, words),
+        QueryNode::Or(nodes) => {
+            let mut acc = EvalResult::empty();
+            for node in nodes {
+                let result = evaluate_query(search_index, node, exact, corrections);
+                acc.pages.union_with(&result.pages);
+                acc.postings.extend(result.postings);
+            }
+            acc
+        }
+        QueryNode::And(required, excluded) => {
+            let Some((first, rest)) = required.split_first() else {
+                return EvalResult::empty();
+            };
+            let mut acc = evaluate_query(search_index, first, exact, corrections);
+            for node in rest {
+                let result = evaluate_query(search_index, node, exact, corrections);
+                acc.pages.intersect_with(&result.pages);
+                acc.postings.extend(result.postings);
+            }
+            for node in excluded {
+                let result = evaluate_query(search_index, node, exact, corrections);
+                acc.pages.difference_with(&result.pages);
+            }
+            acc.postings.retain(|(_, page_word)| acc.pages.contains(page_word.page as usize));
+            acc
+        }
+    }
+}
+
+/// Accumulates each matched page's BM25 score from its term postings.
+fn bm25_scores(search_index: &SearchIndex, postings: &[(f32, &PageWord)]) -> HashMap<usize, f32> {
+    let mut scores: HashMap<usize, f32> = HashMap::new();
+    for (idf, page_word) in postings {
+        let page = page_word.page as usize;
+        let dl = *search_index.page_lengths.get(page).unwrap_or(&0) as f32;
+        let score = bm25_term_score(*idf, page_word.locs.len(), dl, search_index.average_page_length);
+        *scores.entry(page).or_insert(0.0) += score;
+    }
+    scores
+}
+
+fn rank_and_format(search_index: &SearchIndex, results: &BitSet, postings: &[(f32, &PageWord)]) -> String {
+    let scores = bm25_scores(search_index, postings);
+
+    let mut ranked_pages: Vec<usize> = results.iter().collect();
+    ranked_pages.sort_by(|a, b| {
+        let score_a = scores.get(a).copied().unwrap_or(0.0);
+        let score_b = scores.get(b).copied().unwrap_or(0.0);
+        score_b.partial_cmp(&score_a).unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    format_page_results(search_index, &ranked_pages, postings)
+}
+
+/// Matched pages, their owned postings (for scoring/excerpting), and any
+/// fuzzy corrections applied, for a single index.
+type QueryMatches = (BitSet, Vec<(f32, PageWord)>, Vec<(String, String)>);
+
+/// Runs `query` against a single index and returns its matched pages,
+/// owned postings (for scoring/excerpting), and any fuzzy corrections —
+/// the shared core behind `search()` and `search_multi()`.
+fn collect_query_matches(search_index: &SearchIndex, query: &str, exact: bool) -> Option<QueryMatches> {
+    if query::has_operators(query) {
+        let mut corrections = Vec::new();
+        let node = query::parse_query(query);
+        let result = evaluate_query(search_index, &node, exact, &mut corrections);
+        Some((result.pages, result.postings, corrections))
+    } else {
+        keyword_search(search_index, query, exact).map(|matches| {
+            // Keep only postings for pages that survived the AND across
+            // terms, same as the `And` case in `evaluate_query` — a page
+            // that matched some but not all terms isn't a real result and
+            // shouldn't skew scoring (e.g. `search_multi`'s normalization).
+            let postings = matches
+                .words
+                .into_iter()
+                .filter(|(_, page_word)| matches.results.contains(page_word.page as usize))
+                .collect();
+            (matches.results, postings, matches.corrections)
+        })
+    }
+}
+
+#[wasm_bindgen]
+pub fn search(ptr: *mut SearchIndex, query: &str, exact: bool) -> String {
+    let search_index = unsafe { Box::from_raw(ptr) };
+
+    if let Some(generator_version) = search_index.generator_version.as_ref() {
+        if generator_version != search_index.web_version {
+            let _ = Box::into_raw(search_index);
+            return "ERROR: Version mismatch".into();
+        }
+    }
+
+    #[cfg(debug_assertions)]
+    debug_log(&format! {"Searching {:?}", query});
+
+    let o = match collect_query_matches(&search_index, query, exact) {
+        Some((results, postings, corrections)) => {
+            let postings: Vec<(f32, &PageWord)> = postings.iter().map(|(idf, page_word)| (*idf, page_word)).collect();
+            format!(
+                "{}\n{}",
+                format_corrections(&corrections),
+                rank_and_format(&search_index, &results, &postings)
+            )
+        }
+        None => "\n".into(),
+    };
+
     let _ = Box::into_raw(search_index);
 
     #[cfg(debug_assertions)]
@@ -187,11 +641,350 @@ pub fn search(ptr: *mut SearchIndex, query: &str) -> String {
     o
 }
 
+#[wasm_bindgen]
+pub fn search_vector(ptr: *mut SearchIndex, query_vector: &[f32], limit: usize) -> String {
+    let search_index = unsafe { Box::from_raw(ptr) };
+
+    let ranked = search_index.vector_index.search(query_vector, VECTOR_SEARCH_EF, limit);
+    let o = ranked
+        .into_iter()
+        .map(|(page, score)| format!("{}@{score}", search_index.pages[page as usize]))
+        .collect::<Vec<String>>()
+        .join(" ");
+
+    let _ = Box::into_raw(search_index);
+    o
+}
+
+/// Breadth of the HNSW candidate set explored during a vector search.
+const VECTOR_SEARCH_EF: usize = 64;
+
+/// Cosine similarity rescaled from `[-1, 1]` to `[0, 1]`.
+fn normalize_similarity(similarity: f32) -> f32 {
+    (similarity + 1.0) / 2.0
+}
+
+/// Blends keyword and vector search so pages with no literal term overlap
+/// but high semantic similarity still surface, instead of only
+/// re-ranking the keyword matches by vector score. Unions the keyword
+/// path's post-AND matches with the `limit` nearest pages from the HNSW
+/// vector index (the same search `search_vector` exposes on its own),
+/// scoring every page in the union as `(1 - vector_weight) *
+/// normalized_bm25 + vector_weight * normalized_similarity` — a page
+/// with no keyword match scores 0 on the BM25 side, and a page with no
+/// stored embedding scores 0 on the vector side. A keyword match that is
+/// not among the vector hits still gets its own similarity score.
+#[wasm_bindgen]
+pub fn search_hybrid(
+    ptr: *mut SearchIndex,
+    query: &str,
+    exact: bool,
+    query_vector: &[f32],
+    vector_weight: f32,
+    limit: usize,
+) -> String {
+    let search_index = unsafe { Box::from_raw(ptr) };
+
+    let matches = keyword_search(&search_index, query, exact).unwrap_or(KeywordMatches {
+        results: BitSet::new(),
+        scores: HashMap::new(),
+        words: Vec::new(),
+        corrections: Vec::new(),
+    });
+
+    let max_bm25 = matches.scores.values().cloned().fold(0.0_f32, f32::max);
+    let vector_hits = search_index.vector_index.search(query_vector, VECTOR_SEARCH_EF, limit);
+
+    let mut pages = matches.results.clone();
+    for &(page, _) in &vector_hits {
+        pages.insert(page as usize);
+    }
+
+    let mut blended: HashMap<usize, f32> = HashMap::new();
+    for page in &pages {
+        let normalized_bm25 = normalize_score(matches.scores.get(&page).copied().unwrap_or(0.0), max_bm25);
+        let vector_score = search_index
+            .vector_index
+            .similarity_to(query_vector, page as u32)
+            .map(normalize_similarity)
+            .unwrap_or(0.0);
+        blended.insert(
+            page,
+            (1.0 - vector_weight) * normalized_bm25 + vector_weight * vector_score,
+        );
+    }
+
+    let corrections_header = format_corrections(&matches.corrections);
+
+    let mut ranked_pages: Vec<usize> = pages.iter().collect();
+    ranked_pages.sort_by(|a, b| {
+        let score_a = blended.get(a).copied().unwrap_or(0.0);
+        let score_b = blended.get(b).copied().unwrap_or(0.0);
+        score_b.partial_cmp(&score_a).unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    let words: Vec<(f32, &PageWord)> = matches.words.iter().map(|(idf, page_word)| (*idf, page_word)).collect();
+    let o = format!(
+        "{corrections_header}\n{}",
+        format_page_results(&search_index, &ranked_pages, &words)
+    );
+    let _ = Box::into_raw(search_index);
+    o
+}
+
+/// Rescales `score` to `[0, 1]` of `max_score`, so BM25 scores from
+/// different indexes (each computed from that index's own corpus
+/// statistics) become comparable when merged in `search_multi`.
+fn normalize_score(score: f32, max_score: f32) -> f32 {
+    if max_score > 0.0 {
+        score / max_score
+    } else {
+        0.0
+    }
+}
+
+/// Searches several independently-built indexes (e.g. a docs index and a
+/// blog index) and merges them into one globally-ranked result list.
+/// Since each index's BM25 scores are computed from its own corpus
+/// statistics and aren't comparable across indexes, every index's scores
+/// are rescaled to `[0, 1]` of its own best match before merging. Page
+/// ids are namespaced by the position of their source index in `ptrs`,
+/// so the caller can tell which index's page table to resolve a result
+/// against.
+#[wasm_bindgen]
+pub fn search_multi(ptrs: &[u32], query: &str, exact: bool) -> String {
+    let indexes: Vec<Box<SearchIndex>> = ptrs
+        .iter()
+        .map(|&ptr| unsafe { Box::from_raw(ptr as *mut SearchIndex) })
+        .collect();
+
+    let mut corrections: Vec<(String, String)> = Vec::new();
+    let mut ranked: Vec<(usize, usize, f32)> = Vec::new();
+    let mut postings_by_index: Vec<Vec<(f32, PageWord)>> = Vec::with_capacity(indexes.len());
+
+    for (source, search_index) in indexes.iter().enumerate() {
+        let Some((results, postings, mut index_corrections)) = collect_query_matches(search_index, query, exact) else {
+            postings_by_index.push(Vec::new());
+            continue;
+        };
+        corrections.append(&mut index_corrections);
+
+        let borrowed_postings: Vec<(f32, &PageWord)> = postings.iter().map(|(idf, page_word)| (*idf, page_word)).collect();
+        let scores = bm25_scores(search_index, &borrowed_postings);
+        let max_score = scores.values().cloned().fold(0.0_f32, f32::max);
+
+        for page in &results {
+            let normalized = normalize_score(scores.get(&page).copied().unwrap_or(0.0), max_score);
+            ranked.push((source, page, normalized));
+        }
+
+        postings_by_index.push(postings);
+    }
+
+    ranked.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap_or(std::cmp::Ordering::Equal));
+
+    let pages: Vec<String> = ranked
+        .iter()
+        .map(|&(source, page, _)| {
+            let search_index = &indexes[source];
+            let locs: Vec<u32> = postings_by_index[source]
+                .iter()
+                .filter_map(|(_, page_word)| {
+                    if page_word.page as usize == page {
+                        Some(page_word.locs.clone())
+                    } else {
+                        None
+                    }
+                })
+                .flatten()
+                .collect();
+            format!(
+                "{source}:{}@{},{}@{}",
+                &search_index.pages[page],
+                calculate_excerpt(&locs, 30),
+                30,
+                locs.iter().map(|l| l.to_string()).collect::<Vec<String>>().join(",")
+            )
+        })
+        .collect();
+
+    let o = format!("{}\n{}", format_corrections(&corrections), pages.join(" "));
+
+    for index in indexes {
+        let _ = Box::into_raw(index);
+    }
+
+    o
+}
+
+/// Renders the terms that were typo-corrected as `original=correction`
+/// pairs, so the UI can show "searched instead for...".
+fn format_corrections(corrections: &[(String, String)]) -> String {
+    corrections
+        .iter()
+        .map(|(term, correction)| format!("{term}={correction}"))
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
 #[cfg(test)]
 mod tests {
+    use super::*;
+
+    /// A minimal `SearchIndex` with the given words indexed, for tests
+    /// that only care about term/posting resolution.
+    fn test_index(words: HashMap<String, Vec<PageWord>>) -> SearchIndex {
+        SearchIndex {
+            web_version: "test",
+            generator_version: None,
+            language: "en".into(),
+            pages: Vec::new(),
+            page_lengths: Vec::new(),
+            average_page_length: 0.0,
+            chunks: Vec::new(),
+            stops: Vec::new(),
+            words,
+            synonyms: HashMap::new(),
+            vector_index: HnswGraph::new(),
+        }
+    }
+
+    #[test]
+    fn idf_is_near_zero_when_every_page_matches() {
+        // A term present on every page carries almost no discriminating
+        // power — the Okapi "+1" formulation keeps idf positive, but it
+        // should sit close to zero.
+        assert!(bm25_idf(10, 10) < 0.1);
+    }
+
+    #[test]
+    fn idf_is_higher_for_rarer_terms() {
+        let common = bm25_idf(100, 50);
+        let rare = bm25_idf(100, 2);
+        assert!(rare > common, "rare term idf ({rare}) should exceed common term idf ({common})");
+    }
+
+    #[test]
+    fn term_score_increases_with_frequency_but_saturates() {
+        let idf = 1.0;
+        let low = bm25_term_score(idf, 1, 100.0, 100.0);
+        let high = bm25_term_score(idf, 10, 100.0, 100.0);
+        let saturated = bm25_term_score(idf, 1000, 100.0, 100.0);
+
+        assert!(high > low);
+        assert!(saturated > high);
+        // BM25's term-frequency component is bounded by idf * (k1 + 1).
+        assert!(saturated < idf * (BM25_K1 + 1.0));
+    }
+
+    #[test]
+    fn term_score_penalizes_longer_than_average_pages() {
+        let idf = 1.0;
+        let average_length_page = bm25_term_score(idf, 5, 100.0, 100.0);
+        let long_page = bm25_term_score(idf, 5, 400.0, 100.0);
+
+        assert!(long_page < average_length_page);
+    }
+
+    #[test]
+    fn stemmer_for_language_covers_every_mapped_code() {
+        for language in [
+            "ar", "da", "nl", "en", "fi", "fr", "de", "el", "hu", "it", "no", "pt", "ro", "ru", "es", "sv", "ta", "tr",
+        ] {
+            assert!(stemmer_for_language(language).is_some(), "{language} should have a stemmer");
+        }
+    }
+
+    #[test]
+    fn stemmer_for_language_falls_back_to_none_for_unmapped_codes() {
+        assert!(stemmer_for_language("zz").is_none());
+    }
+
     #[test]
-    fn it_works() {
-        let result = 2 + 2;
-        assert_eq!(result, 4);
+    fn stem_term_leaves_words_unchanged_without_a_stemmer() {
+        assert_eq!(stem_term(None, "running"), "running");
+    }
+
+    #[test]
+    fn stem_term_reduces_words_to_their_stem() {
+        let stemmer = stemmer_for_language("en").unwrap();
+        assert_eq!(stem_term(Some(&stemmer), "running"), "run");
+    }
+
+    #[test]
+    fn term_variants_includes_synonyms_and_next_term_concatenation() {
+        let mut synonyms = HashMap::new();
+        synonyms.insert("fast".to_owned(), vec!["quick".to_owned(), "speedy".to_owned()]);
+
+        let variants = term_variants("wi", Some("fi"), &synonyms);
+        assert_eq!(variants, vec!["wi", "wifi"]);
+
+        let variants = term_variants("fast", None, &synonyms);
+        assert_eq!(variants, vec!["fast", "quick", "speedy"]);
+    }
+
+    #[test]
+    fn term_variants_does_not_split_the_term_itself() {
+        // Splitting is handled separately by compound_split_matches, which
+        // requires the halves to be adjacent rather than OR-ing them in.
+        let variants = term_variants("email", None, &HashMap::new());
+        assert_eq!(variants, vec!["email"]);
+    }
+
+    #[test]
+    fn compound_split_matches_requires_adjacent_halves() {
+        let mut words = HashMap::new();
+        words.insert("e".to_owned(), vec![PageWord { page: 0, locs: vec![0] }]);
+        words.insert("mail".to_owned(), vec![PageWord { page: 0, locs: vec![1] }]);
+        let search_index = test_index(words);
+
+        let matched = compound_split_matches(&search_index, "email");
+        assert_eq!(matched.len(), 1);
+        assert_eq!(matched[0].page, 0);
+        assert_eq!(matched[0].locs, vec![0, 1]);
+    }
+
+    #[test]
+    fn compound_split_matches_ignores_a_page_with_only_one_half() {
+        // A page that only contains "mail" (never preceded by "e") must
+        // not be treated as a match for "email".
+        let mut words = HashMap::new();
+        words.insert("mail".to_owned(), vec![PageWord { page: 0, locs: vec![5] }]);
+        let search_index = test_index(words);
+
+        assert!(compound_split_matches(&search_index, "email").is_empty());
+    }
+
+    #[test]
+    fn normalize_score_rescales_to_the_index_best_match() {
+        assert_eq!(normalize_score(2.5, 5.0), 0.5);
+        assert_eq!(normalize_score(5.0, 5.0), 1.0);
+    }
+
+    #[test]
+    fn normalize_score_is_zero_when_the_index_has_no_matches() {
+        assert_eq!(normalize_score(0.0, 0.0), 0.0);
+    }
+
+    #[test]
+    fn search_hybrid_surfaces_vector_only_pages_with_no_keyword_overlap() {
+        let mut words = HashMap::new();
+        words.insert("cat".to_owned(), vec![PageWord { page: 0, locs: vec![0] }]);
+        let mut search_index = test_index(words);
+        search_index.pages = vec!["a.html".into(), "b.html".into()];
+        search_index.page_lengths = vec![10, 10];
+        search_index.average_page_length = 10.0;
+        // Page 0 only matches the keyword "cat"; page 1 only matches the
+        // query vector, with no keyword overlap at all.
+        search_index.vector_index.insert_vector(0, vec![1.0, 0.0]);
+        search_index.vector_index.insert_vector(1, vec![0.0, 1.0]);
+        search_index.vector_index.insert_layer(0, 0, vec![1]);
+        search_index.vector_index.insert_layer(0, 1, vec![0]);
+        search_index.vector_index.set_entry_point(0);
+
+        let ptr = Box::into_raw(Box::new(search_index));
+        let result = search_hybrid(ptr, "cat", false, &[0.0, 1.0], 0.8, 5);
+
+        assert!(result.contains("b.html"), "vector-only page should surface: {result}");
     }
 }
\ No newline at end of file
@@ -0,0 +1,60 @@
+use std::collections::HashMap;
+
+use crate::util::{ByteReader, IndexError};
+use crate::{IndexChunk, SearchIndex};
+
+impl SearchIndex {
+    /// Decodes the top-level metadata blob: generator version, language,
+    /// the page table (with per-page lengths, for BM25), stop words, and
+    /// the list of index chunks available to be fetched on demand.
+    pub fn decode_metadata(&mut self, bytes: &[u8]) -> Result<(), IndexError> {
+        let mut reader = ByteReader::new(bytes);
+
+        let has_generator_version = reader.read_u8()? == 1;
+        self.generator_version = if has_generator_version {
+            Some(reader.read_string()?)
+        } else {
+            None
+        };
+
+        self.language = reader.read_string()?;
+
+        let page_count = reader.read_u32()? as usize;
+        self.pages = Vec::with_capacity(page_count);
+        self.page_lengths = Vec::with_capacity(page_count);
+        let mut total_length: u64 = 0;
+        for _ in 0..page_count {
+            self.pages.push(reader.read_string()?);
+            let length = reader.read_u32()?;
+            self.page_lengths.push(length);
+            total_length += length as u64;
+        }
+        self.average_page_length = if page_count > 0 {
+            total_length as f32 / page_count as f32
+        } else {
+            0.0
+        };
+
+        self.stops = reader.read_string_vec()?;
+
+        let chunk_count = reader.read_u32()? as usize;
+        self.chunks = Vec::with_capacity(chunk_count);
+        for _ in 0..chunk_count {
+            self.chunks.push(IndexChunk {
+                from: reader.read_string()?,
+                to: reader.read_string()?,
+                hash: reader.read_string()?,
+            });
+        }
+
+        let synonym_count = reader.read_u32()? as usize;
+        self.synonyms = HashMap::with_capacity(synonym_count);
+        for _ in 0..synonym_count {
+            let term = reader.read_string()?;
+            let group = reader.read_string_vec()?;
+            self.synonyms.insert(term, group);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,233 @@
+/// AST for a parsed query. Leaf terms and phrase words are kept as the
+/// raw, unstemmed tokens the user typed — stemming/synonym/fuzzy
+/// resolution happens in `lib.rs` where the `SearchIndex` is available.
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueryNode {
+    Term(String),
+    Phrase(Vec<String>),
+    Or(Vec<QueryNode>),
+    /// An implicit-AND group: `required` terms must all match, `excluded`
+    /// terms (from a leading `-`) must all be absent.
+    And(Vec<QueryNode>, Vec<QueryNode>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Phrase(Vec<String>),
+    Minus,
+    Or,
+    LParen,
+    RParen,
+}
+
+fn tokenize(query: &str) -> Vec<Token> {
+    let chars: Vec<char> = query.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        match chars[i] {
+            ' ' => i += 1,
+            '(' => {
+                tokens.push(Token::LParen);
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Token::RParen);
+                i += 1;
+            }
+            '-' => {
+                tokens.push(Token::Minus);
+                i += 1;
+            }
+            '"' => {
+                i += 1;
+                let start = i;
+                while i < chars.len() && chars[i] != '"' {
+                    i += 1;
+                }
+                let phrase: String = chars[start..i].iter().collect();
+                if i < chars.len() {
+                    i += 1; // skip closing quote
+                }
+                tokens.push(Token::Phrase(phrase.split_whitespace().map(str::to_owned).collect()));
+            }
+            _ => {
+                let start = i;
+                while i < chars.len() && !matches!(chars[i], ' ' | '(' | ')' | '"') {
+                    i += 1;
+                }
+                let word: String = chars[start..i].iter().collect();
+                tokens.push(if word == "OR" { Token::Or } else { Token::Word(word) });
+            }
+        }
+    }
+
+    tokens
+}
+
+/// Parses a query string supporting implicit AND, explicit `OR`, a
+/// leading `-` for NOT, quoted `"exact phrases"`, and `(grouping)`.
+pub fn parse_query(query: &str) -> QueryNode {
+    let tokens = tokenize(query);
+    let mut pos = 0;
+    parse_or(&tokens, &mut pos)
+}
+
+fn parse_or(tokens: &[Token], pos: &mut usize) -> QueryNode {
+    let mut nodes = vec![parse_and(tokens, pos)];
+    while matches!(tokens.get(*pos), Some(Token::Or)) {
+        *pos += 1;
+        nodes.push(parse_and(tokens, pos));
+    }
+    if nodes.len() == 1 {
+        nodes.pop().unwrap()
+    } else {
+        QueryNode::Or(nodes)
+    }
+}
+
+fn parse_and(tokens: &[Token], pos: &mut usize) -> QueryNode {
+    let mut required = Vec::new();
+    let mut excluded = Vec::new();
+
+    loop {
+        match tokens.get(*pos) {
+            Some(Token::Minus) => {
+                *pos += 1;
+                if let Some(node) = parse_primary(tokens, pos) {
+                    excluded.push(node);
+                }
+            }
+            Some(Token::Word(_)) | Some(Token::Phrase(_)) | Some(Token::LParen) => {
+                if let Some(node) = parse_primary(tokens, pos) {
+                    required.push(node);
+                }
+            }
+            _ => break,
+        }
+    }
+
+    QueryNode::And(required, excluded)
+}
+
+fn parse_primary(tokens: &[Token], pos: &mut usize) -> Option<QueryNode> {
+    match tokens.get(*pos)?.clone() {
+        Token::Word(word) => {
+            *pos += 1;
+            Some(QueryNode::Term(word))
+        }
+        Token::Phrase(words) => {
+            *pos += 1;
+            Some(QueryNode::Phrase(words))
+        }
+        Token::LParen => {
+            *pos += 1;
+            let node = parse_or(tokens, pos);
+            if matches!(tokens.get(*pos), Some(Token::RParen)) {
+                *pos += 1;
+            }
+            Some(node)
+        }
+        Token::Minus | Token::Or | Token::RParen => None,
+    }
+}
+
+/// All leaf term/phrase words referenced anywhere in the query, so the
+/// caller can make sure every chunk they live in gets requested.
+pub fn collect_terms(node: &QueryNode, out: &mut Vec<String>) {
+    match node {
+        QueryNode::Term(word) => out.push(word.clone()),
+        QueryNode::Phrase(words) => out.extend(words.iter().cloned()),
+        QueryNode::Or(nodes) => {
+            for node in nodes {
+                collect_terms(node, out);
+            }
+        }
+        QueryNode::And(required, excluded) => {
+            for node in required.iter().chain(excluded) {
+                collect_terms(node, out);
+            }
+        }
+    }
+}
+
+/// Whether `query` uses any boolean/phrase/grouping syntax, so callers can
+/// keep the simpler implicit-AND path for plain queries.
+pub fn has_operators(query: &str) -> bool {
+    query.contains('"')
+        || query.contains('(')
+        || query.contains(')')
+        || query.split(' ').any(|token| token == "OR")
+        || query.starts_with('-')
+        || query.contains(" -")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn term(word: &str) -> QueryNode {
+        QueryNode::Term(word.into())
+    }
+
+    /// An implicit-AND group of a single required node, which is what
+    /// `parse_and` produces even when there's nothing to actually AND.
+    fn and1(node: QueryNode) -> QueryNode {
+        QueryNode::And(vec![node], vec![])
+    }
+
+    #[test]
+    fn implicit_and_of_plain_terms() {
+        assert_eq!(parse_query("cat dog"), QueryNode::And(vec![term("cat"), term("dog")], vec![]));
+    }
+
+    #[test]
+    fn leading_minus_excludes_a_term() {
+        assert_eq!(parse_query("cat -dog"), QueryNode::And(vec![term("cat")], vec![term("dog")]));
+    }
+
+    #[test]
+    fn quoted_phrase_keeps_its_words_together() {
+        assert_eq!(
+            parse_query("\"cat dog\""),
+            and1(QueryNode::Phrase(vec!["cat".into(), "dog".into()]))
+        );
+    }
+
+    #[test]
+    fn or_binds_looser_than_implicit_and() {
+        // "cat dog OR bird" should parse as (cat AND dog) OR bird, not
+        // cat AND (dog OR bird).
+        assert_eq!(
+            parse_query("cat dog OR bird"),
+            QueryNode::Or(vec![
+                QueryNode::And(vec![term("cat"), term("dog")], vec![]),
+                and1(term("bird")),
+            ])
+        );
+    }
+
+    #[test]
+    fn parens_group_an_or_before_anding_with_the_next_term() {
+        // "(cat OR dog) bird" should parse as (cat OR dog) AND bird.
+        assert_eq!(
+            parse_query("(cat OR dog) bird"),
+            QueryNode::And(
+                vec![QueryNode::Or(vec![and1(term("cat")), and1(term("dog"))]), term("bird")],
+                vec![]
+            )
+        );
+    }
+
+    #[test]
+    fn has_operators_detects_each_operator_kind() {
+        assert!(!has_operators("cat dog"));
+        assert!(has_operators("\"cat dog\""));
+        assert!(has_operators("(cat dog)"));
+        assert!(has_operators("cat OR dog"));
+        assert!(has_operators("-cat dog"));
+        assert!(has_operators("cat -dog"));
+    }
+}
@@ -0,0 +1,96 @@
+use std::convert::TryInto;
+use std::fmt;
+
+/// Minimal little-endian cursor over a metadata/chunk byte slice.
+///
+/// Pagefind's index files are a flat, length-prefixed binary format rather
+/// than a general-purpose serialization (keeps the wasm binary small).
+pub struct ByteReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+#[derive(Debug)]
+pub enum IndexError {
+    Corrupt(&'static str),
+}
+
+impl fmt::Display for IndexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IndexError::Corrupt(msg) => write!(f, "corrupt index data: {msg}"),
+        }
+    }
+}
+
+impl<'a> ByteReader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, pos: 0 }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], IndexError> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .ok_or(IndexError::Corrupt("length overflow"))?;
+        let slice = self
+            .bytes
+            .get(self.pos..end)
+            .ok_or(IndexError::Corrupt("unexpected end of data"))?;
+        self.pos = end;
+        Ok(slice)
+    }
+
+    pub fn read_u8(&mut self) -> Result<u8, IndexError> {
+        Ok(self.take(1)?[0])
+    }
+
+    pub fn read_u32(&mut self) -> Result<u32, IndexError> {
+        let slice: [u8; 4] = self
+            .take(4)?
+            .try_into()
+            .map_err(|_| IndexError::Corrupt("bad u32"))?;
+        Ok(u32::from_le_bytes(slice))
+    }
+
+    pub fn read_f32(&mut self) -> Result<f32, IndexError> {
+        let slice: [u8; 4] = self
+            .take(4)?
+            .try_into()
+            .map_err(|_| IndexError::Corrupt("bad f32"))?;
+        Ok(f32::from_le_bytes(slice))
+    }
+
+    pub fn read_string(&mut self) -> Result<String, IndexError> {
+        let len = self.read_u32()? as usize;
+        let bytes = self.take(len)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| IndexError::Corrupt("bad utf8"))
+    }
+
+    pub fn read_u32_vec(&mut self) -> Result<Vec<u32>, IndexError> {
+        let len = self.read_u32()? as usize;
+        let mut out = Vec::with_capacity(len);
+        for _ in 0..len {
+            out.push(self.read_u32()?);
+        }
+        Ok(out)
+    }
+
+    pub fn read_f32_vec(&mut self) -> Result<Vec<f32>, IndexError> {
+        let len = self.read_u32()? as usize;
+        let mut out = Vec::with_capacity(len);
+        for _ in 0..len {
+            out.push(self.read_f32()?);
+        }
+        Ok(out)
+    }
+
+    pub fn read_string_vec(&mut self) -> Result<Vec<String>, IndexError> {
+        let len = self.read_u32()? as usize;
+        let mut out = Vec::with_capacity(len);
+        for _ in 0..len {
+            out.push(self.read_string()?);
+        }
+        Ok(out)
+    }
+}